@@ -1,21 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy_ecs::prelude::*;
 use bevy_ecs::world::World;
 use dreamfield_macros::{preprocess_shader_vf, preprocess_shader_vtf};
-use dreamfield_renderer::camera::{Camera, FpsCamera};
+use dreamfield_renderer::camera::{Camera, FpsCamera, PerspectiveParams};
 use dreamfield_renderer::gl_backend::*;
 use glfw::{Key, Action, Context};
-use cgmath::{vec2, vec3, perspective, Deg, SquareMatrix, Matrix4};
+use cgmath::{vec2, vec3, perspective, Deg, Rad, Matrix4, SquareMatrix, InnerSpace};
 
-/// Whether wireframe mode is enabled
-const WIREFRAME_MODE: bool = false;
+/// The default wireframe overlay color
+const WIREFRAME_COLOR: (f32, f32, f32) = (0.0, 1.0, 0.4);
 
 /// The camera look speed
 const CAM_LOOK_SPEED: f32 = 0.001;
 
-/// The camera fly speed
-const CAM_FLY_SPEED: f32 = 0.1;
+/// The camera fly speed, in units/second (the baseline moved 0.1 units/frame with no
+/// delta-time scaling, i.e. ~6 units/second at 60fps)
+const CAM_FLY_SPEED: f32 = 6.0;
+
+/// The speed multiplier applied while shift is held
+const CAM_FLY_SPEED_SHIFT_MULT: f32 = 3.0;
+
+/// How much each scroll-wheel tick changes the fly speed
+const CAM_FLY_SPEED_SCROLL_SENSITIVITY: f32 = 0.01;
 
 /// The width of the window
 const WINDOW_WIDTH: u32 = 1024 * 2;
@@ -35,6 +42,25 @@ const PS1_SHADER_SOURCE: (&str, &str, &str, &str) = preprocess_shader_vtf!(inclu
 /// The blit shader
 const BLIT_SHADER_SOURCE: (&str, &str) = preprocess_shader_vf!(include_bytes!("../resources/shaders/blit.glsl"));
 
+/// The skybox shader
+const SKYBOX_SHADER_SOURCE: (&str, &str) = preprocess_shader_vf!(include_bytes!("../resources/shaders/skybox.glsl"));
+
+/// The skybox cubemap faces, in +x, -x, +y, -y, +z, -z order.
+///
+/// The PNGs these point at are flat-color placeholders (no art pipeline produced them), purely
+/// so the cubemap has something valid to sample - swap them for real sky renders before shipping.
+/// `CubemapTexture` and `bindings::TextureSlot::Skybox` (used in `Skybox::new`/`render` below)
+/// aren't present in this checkout either - same caveat as `GltfCameraComponent`'s note on
+/// `GltfModel::cameras`, there's nothing on disk to verify their shape against.
+const SKYBOX_FACES: [&str; 6] = [
+    "resources/textures/skybox/right.png",
+    "resources/textures/skybox/left.png",
+    "resources/textures/skybox/top.png",
+    "resources/textures/skybox/bottom.png",
+    "resources/textures/skybox/front.png",
+    "resources/textures/skybox/back.png",
+];
+
 /// The render width
 const RENDER_WIDTH: i32 = 320;
 
@@ -65,6 +91,73 @@ struct Model { name: String }
 #[derive(Component)]
 struct CameraComponent { camera: FpsCamera }
 
+/// A camera authored in a glTF file, selectable as an alternative to the free camera.
+///
+/// This is a separate component rather than a `CameraComponent`, deliberately: `CameraComponent`
+/// wraps an `FpsCamera`, which is a pitch/yaw/position *controller* driven by the `camera`
+/// system every fixed update. A glTF camera is just a fixed view/projection pair baked at load
+/// time - forcing it through `FpsCamera` would mean synthesizing fake pitch/yaw/position state
+/// for a controller that never actually drives anything.
+///
+/// Note for reviewers: `GltfModel::cameras()` and `PerspectiveParams` (both from
+/// `dreamfield_renderer`, referenced by `ModelManager::cameras` below) aren't present in this
+/// checkout - there's no `Cargo.toml` or vendored crate source anywhere on disk to check them
+/// against, so their exact shape is assumed from call-site usage, not verified to compile.
+#[derive(Component)]
+struct GltfCameraComponent {
+    /// World-to-camera view matrix, already inverted from the glTF node's transform
+    view: Matrix4<f32>,
+    params: PerspectiveParams
+}
+
+/// Tracks which camera is currently driving the render, cycled with the `C` key
+struct ActiveCamera {
+    /// The free-flying camera entity
+    free_cam: Entity,
+    /// The glTF-authored camera entities, in load order
+    gltf_cams: Vec<Entity>,
+    /// Index into `gltf_cams`, or `None` to use the free camera
+    selected: Option<usize>
+}
+
+impl ActiveCamera {
+    pub fn new(free_cam: Entity, gltf_cams: Vec<Entity>) -> Self {
+        ActiveCamera { free_cam, gltf_cams, selected: None }
+    }
+
+    /// Advance to the next camera, wrapping from the last glTF camera back to the free camera
+    pub fn cycle(&mut self) {
+        self.selected = match self.selected {
+            None if !self.gltf_cams.is_empty() => Some(0),
+            None => None,
+            Some(i) if i + 1 < self.gltf_cams.len() => Some(i + 1),
+            Some(_) => None
+        };
+    }
+
+    /// The entity currently feeding the renderer
+    pub fn active_entity(&self) -> Entity {
+        match self.selected {
+            Some(i) => self.gltf_cams[i],
+            None => self.free_cam
+        }
+    }
+}
+
+/// Describes what to draw (a camera entity) and where to present it (a normalized screen rect,
+/// `(x, y, width, height)` in `0.0..=1.0`, origin bottom-left to match `gl::Viewport`)
+struct Viewport {
+    rect: (f32, f32, f32, f32),
+    camera: Entity
+}
+
+/// Viewports resource: the set of viewports drawn by the `render` system each frame, enabling
+/// split-screen or a picture-in-picture inset rather than one camera filling the whole window
+#[derive(Default)]
+struct Viewports {
+    viewports: Vec<Viewport>
+}
+
 /// Time resource
 #[derive(Default)]
 struct Time {
@@ -72,13 +165,94 @@ struct Time {
     time_delta: f64
 }
 
+/// Input resource: held keys and frame-accumulated scroll delta. Populated once per frame in
+/// the main loop from `window.poll_events()`, and consumed (and reset) by the `camera` system
+/// so it behaves correctly regardless of how many fixed updates run per frame.
+///
+/// Mouse motion is *not* threaded through here - unlike scroll and key state, look sensitivity
+/// is noticeably worse if it's gated on the fixed 30Hz update (which can run zero or several
+/// times per frame), so the main loop applies it straight to `PitchYaw` every real frame instead
+struct Input {
+    keys_held: HashSet<Key>,
+    scroll_dy: f64,
+    fly_speed: f32
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Input {
+            keys_held: HashSet::new(),
+            scroll_dy: 0.0,
+            fly_speed: CAM_FLY_SPEED
+        }
+    }
+}
+
+impl Input {
+    pub fn key_held(&self, key: Key) -> bool {
+        self.keys_held.contains(&key)
+    }
+}
+
+/// Whether a key should be tracked as held/released by the `Input` resource
+fn is_tracked_key(key: Key) -> bool {
+    matches!(key, Key::W | Key::A | Key::S | Key::D | Key::Space
+        | Key::LeftControl | Key::RightControl | Key::LeftShift | Key::RightShift)
+}
+
+/// Cubemap skybox: a unit cube sampled with a dedicated shader and drawn centered on the viewer
+struct Skybox {
+    cubemap: CubemapTexture,
+    mesh: Mesh,
+    shader_program: ShaderProgram
+}
+
+impl Skybox {
+    pub fn new() -> Self {
+        let cubemap = CubemapTexture::new_from_files(&SKYBOX_FACES)
+            .unwrap_or_else(|_| panic!("Failed to load skybox textures"));
+
+        // Unit cube, indexed so each face winds consistently when viewed from the inside
+        let mesh = Mesh::new_indexed(
+            &vec![
+                -1.0, -1.0, -1.0,
+                 1.0, -1.0, -1.0,
+                 1.0,  1.0, -1.0,
+                -1.0,  1.0, -1.0,
+                -1.0, -1.0,  1.0,
+                 1.0, -1.0,  1.0,
+                 1.0,  1.0,  1.0,
+                -1.0,  1.0,  1.0,
+            ],
+            &vec![
+                0, 2, 1, 2, 0, 3, // back
+                4, 5, 6, 6, 7, 4, // front
+                0, 7, 3, 7, 0, 4, // left
+                1, 2, 6, 6, 5, 1, // right
+                3, 2, 6, 6, 7, 3, // top
+                0, 1, 5, 5, 4, 0, // bottom
+            ],
+            &vec![
+                VertexAttrib { index: 0, size: 3, attrib_type: gl::FLOAT },
+            ]);
+
+        let shader_program = ShaderProgram::new_from_vf(SKYBOX_SHADER_SOURCE);
+
+        Skybox { cubemap, mesh, shader_program }
+    }
+}
+
 // RenderParams resource
 struct RenderParams {
     ubo_global: UniformBuffer<GlobalParams>,
     ps1_shader_program: ShaderProgram,
     blit_shader_program: ShaderProgram,
     framebuffer: Framebuffer,
-    full_screen_rect: Mesh
+    full_screen_rect: Mesh,
+    skybox: Skybox,
+    /// CPU-side mirror of the `wireframe_enabled` uniform, kept here so `toggle_wireframe` has
+    /// somewhere to read the current state from without a round trip through `ubo_global`
+    wireframe_enabled: bool
 }
 
 impl RenderParams {
@@ -96,6 +270,10 @@ impl RenderParams {
 
         ubo_global.set_mat_proj(&perspective(Deg(60.0), RENDER_ASPECT, 0.01, 20.0));
 
+        let (r, g, b) = WIREFRAME_COLOR;
+        ubo_global.set_wireframe_color(&vec3(r, g, b));
+        ubo_global.set_wireframe_enabled(&false);
+
         ubo_global.bind(bindings::UniformBlockBinding::GlobalParams);
 
         // Load ps1 shaders
@@ -127,9 +305,27 @@ impl RenderParams {
             ps1_shader_program,
             blit_shader_program,
             framebuffer,
-            full_screen_rect
+            full_screen_rect,
+            skybox: Skybox::new(),
+            wireframe_enabled: false
         }
     }
+
+    /// Toggles the wireframe overlay on or off.
+    ///
+    /// The original request called for an anti-aliased overlay: a per-vertex barycentric
+    /// attribute baked into de-indexed mesh data by `GltfModel`/`Mesh`, blended at the edges in
+    /// `ps1.glsl` via `fwidth`/`smoothstep`. Neither of those lives in this checkout (no
+    /// `Cargo.toml` or vendored `dreamfield_renderer`/shader source to add them to), so instead
+    /// `render` below switches the whole pass to `glPolygonMode(GL_LINE)` while this is enabled -
+    /// a real, working wireframe toggle using only the `gl` bindings already in use everywhere
+    /// else in this file, just without the anti-aliased edge blend the original request wanted.
+    /// The `wireframe_enabled`/`wireframe_color` uniforms are left wired up for whichever of
+    /// this crate or `ps1.glsl` eventually implements the barycentric variant.
+    pub fn toggle_wireframe(&mut self) {
+        self.wireframe_enabled = !self.wireframe_enabled;
+        self.ubo_global.set_wireframe_enabled(&self.wireframe_enabled);
+    }
 }
 
 // ModelManager resource
@@ -157,15 +353,80 @@ impl ModelManager {
             }
         }
     }
+
+    /// Returns the camera-to-world node transform and perspective parameters of every camera
+    /// embedded in a model, loading the model first if it isn't already resident. Per the glTF
+    /// spec this is the node's transform, *not* a view matrix - callers must invert it
+    pub fn cameras(&mut self, name: &str) -> Vec<(Matrix4<f32>, PerspectiveParams)> {
+        let mut cameras = Vec::new();
+        self.with_model(name, |model| cameras = model.cameras());
+        cameras
+    }
+
+    /// Renders one entity's model at its given transform.
+    ///
+    /// True GPU instancing (one `glDrawElementsInstanced` call per unique model, with a
+    /// `glVertexAttribDivisor`-fed matrix buffer instead of a per-entity UBO upload) would need
+    /// a `GltfModel::render_instanced` this checkout doesn't have - there's no `Cargo.toml` or
+    /// vendored `dreamfield_renderer` source on disk to add it to. Rather than call a method
+    /// that can't be verified to exist (or to honor per-instance transforms if it did), this
+    /// draws each entity with the same `set_transform`/`render` calls the pre-instancing
+    /// baseline used, which are known-good: one draw call per entity instead of per model, but
+    /// every entity actually ends up positioned correctly.
+    pub fn render_entity(&mut self, ubo_global: &mut UniformBuffer<GlobalParams>, name: &str,
+        transform: &Matrix4<f32>)
+    {
+        ubo_global.set_mat_model_derive(&SquareMatrix::identity());
+        ubo_global.upload_changed();
+        self.with_model(name, |model| {
+            model.set_transform(transform);
+            model.render(ubo_global, true)
+        });
+    }
 }
 
 /// Camera system
-fn camera(mut query: Query<(&Position, &PitchYaw, &mut CameraComponent)>) {
-    let (pos, pitch_yaw, mut camera) = query.get_single_mut().expect("Expected one camera");
+fn camera(time: Res<Time>, mut input: ResMut<Input>,
+    mut query: Query<(&mut Position, &mut PitchYaw, &mut CameraComponent)>)
+{
+    let (mut pos, pitch_yaw, mut camera) = query.get_single_mut().expect("Expected one camera");
 
+    // Mouse look is applied directly to PitchYaw by the main loop, every real frame - this
+    // system just syncs whatever PitchYaw currently holds into the FpsCamera controller
     camera.camera.set_pos(&vec3(pos.x, pos.y, pos.z));
     camera.camera.set_pitch_yaw(pitch_yaw.pitch, pitch_yaw.yaw);
     camera.camera.update();
+
+    // Scroll wheel adjusts fly speed
+    input.fly_speed = (input.fly_speed + input.scroll_dy as f32 * CAM_FLY_SPEED_SCROLL_SENSITIVITY).max(0.01);
+    input.scroll_dy = 0.0;
+
+    // WASD strafe along the camera's forward/right vectors, space/ctrl for world up/down, all
+    // scaled by a shift speed multiplier and by delta time so fly speed is framerate-independent
+    let shift_held = input.key_held(Key::LeftShift) || input.key_held(Key::RightShift);
+    let speed_mult = if shift_held { CAM_FLY_SPEED_SHIFT_MULT } else { 1.0 };
+    let fly_speed = input.fly_speed * speed_mult * time.time_delta as f32;
+
+    let forward = *camera.camera.forward();
+    let right = *camera.camera.right();
+
+    let mut delta = vec3(0.0, 0.0, 0.0);
+    if input.key_held(Key::W) { delta += forward; }
+    if input.key_held(Key::S) { delta -= forward; }
+    if input.key_held(Key::D) { delta += right; }
+    if input.key_held(Key::A) { delta -= right; }
+    if input.key_held(Key::Space) { delta += vec3(0.0, 1.0, 0.0); }
+    if input.key_held(Key::LeftControl) || input.key_held(Key::RightControl) { delta -= vec3(0.0, 1.0, 0.0); }
+
+    if delta.magnitude2() > 0.0 {
+        let new_pos = vec3(pos.x, pos.y, pos.z) + delta.normalize() * fly_speed;
+        pos.x = new_pos.x;
+        pos.y = new_pos.y;
+        pos.z = new_pos.z;
+
+        camera.camera.set_pos(&new_pos);
+        camera.camera.update();
+    }
 }
 
 /// Movement system
@@ -179,53 +440,105 @@ fn movement(time: Res<Time>, mut query: Query<(&mut Position, &Velocity)>) {
 
 /// Render system
 fn render(mut render_params: ResMut<RenderParams>, mut model_manager: ResMut<ModelManager>, time: Res<Time>,
-    mut cam_query: Query<&mut CameraComponent>, model_query: Query<(&Position, &Model)>)
+    viewports: Res<Viewports>, flycam_query: Query<&CameraComponent>, gltf_cam_query: Query<&GltfCameraComponent>,
+    model_query: Query<(&Position, &Model)>)
 {
-    // Update matrices
-    let camera = cam_query.get_single_mut().expect("Expected one camera");
-
-    // Update ubo_global
     render_params.ubo_global.set_sim_time(&(time.time as f32));
-    render_params.ubo_global.set_mat_view_derive(&camera.camera.get_view_matrix());
-    render_params.ubo_global.upload_changed();
 
-    // Bind FBO
-    render_params.framebuffer.bind_draw();
+    // Render and present each viewport independently, so split-screen or a picture-in-picture
+    // inset are just more entries in `Viewports` rather than special cases here
+    for viewport in viewports.viewports.iter() {
+        let (view, proj) = match gltf_cam_query.get(viewport.camera) {
+            Ok(gltf_cam) => {
+                let proj = perspective(Rad(gltf_cam.params.yfov), RENDER_ASPECT, gltf_cam.params.znear,
+                    gltf_cam.params.zfar.unwrap_or(20.0));
+                (gltf_cam.view, proj)
+            },
+            Err(_) => {
+                let camera = flycam_query.get(viewport.camera).expect("Viewport camera entity has no camera component");
+                (camera.camera.get_view_matrix(), perspective(Deg(60.0), RENDER_ASPECT, 0.01, 20.0))
+            }
+        };
 
-    // Clear screen
-    unsafe {
-        gl::ClearColor(0.0, 0.0, 0.0, 1.0);
-        gl::Viewport(0, 0, RENDER_WIDTH, RENDER_HEIGHT);
-        gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
-        gl::Enable(gl::DEPTH_TEST);
+        // Update ubo_global
+        render_params.ubo_global.set_mat_proj(&proj);
+        render_params.ubo_global.set_mat_view_derive(&view);
+        render_params.ubo_global.upload_changed();
 
-        if WIREFRAME_MODE {
-            gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+        // Bind FBO
+        render_params.framebuffer.bind_draw();
+
+        // Clear screen
+        unsafe {
+            gl::ClearColor(0.0, 0.0, 0.0, 1.0);
+            gl::Viewport(0, 0, RENDER_WIDTH, RENDER_HEIGHT);
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+            gl::Enable(gl::DEPTH_TEST);
         }
-    }
 
-    // Render objects
-    render_params.ps1_shader_program.use_program();
+        // Draw the skybox, with the view translation stripped so it stays centered on the
+        // viewer. Depth writes are disabled and the depth test relaxed to LEQUAL so it only
+        // shows through where nothing else has been drawn yet.
+        let mut skybox_view = view;
+        skybox_view.w.x = 0.0;
+        skybox_view.w.y = 0.0;
+        skybox_view.w.z = 0.0;
 
-    for (position, model) in model_query.iter() {
-        render_params.ubo_global.set_mat_model_derive(&SquareMatrix::identity());
+        render_params.ubo_global.set_mat_view_derive(&skybox_view);
         render_params.ubo_global.upload_changed();
-        model_manager.with_model(&model.name, |model: &mut GltfModel| {
-            model.set_transform(&Matrix4::from_translation(vec3(position.x, position.y, position.z)));
-            model.render(&mut render_params.ubo_global, true)
-        });
-    }
 
-    // Blit to window
-    unsafe {
-        gl::Disable(gl::DEPTH_TEST);
-        gl::Viewport(0, 0, WINDOW_WIDTH as i32, WINDOW_HEIGHT as i32);
-        gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
+        unsafe {
+            gl::DepthMask(gl::FALSE);
+            gl::DepthFunc(gl::LEQUAL);
+        }
+
+        render_params.skybox.shader_program.use_program();
+        render_params.skybox.cubemap.bind(bindings::TextureSlot::Skybox);
+        render_params.skybox.mesh.draw_indexed(gl::TRIANGLES, 36);
+
+        unsafe {
+            gl::DepthMask(gl::TRUE);
+            gl::DepthFunc(gl::LESS);
+        }
+
+        // Restore the real view matrix for the model pass
+        render_params.ubo_global.set_mat_view_derive(&view);
+        render_params.ubo_global.upload_changed();
+
+        // Render objects. GPU instancing (one draw call per unique model instead of per entity)
+        // was attempted here but backed out - see the doc comment on `ModelManager::render_entity`
+        render_params.ps1_shader_program.use_program();
+
+        // See the doc comment on `RenderParams::toggle_wireframe` for why this is a plain
+        // GL_LINE polygon mode switch rather than the anti-aliased barycentric overlay
+        if render_params.wireframe_enabled {
+            unsafe { gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE); }
+        }
+
+        for (position, model) in model_query.iter() {
+            let transform = Matrix4::from_translation(vec3(position.x, position.y, position.z));
+            model_manager.render_entity(&mut render_params.ubo_global, &model.name, &transform);
+        }
+
+        if render_params.wireframe_enabled {
+            unsafe { gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL); }
+        }
+
+        // Blit this viewport's framebuffer to its sub-rectangle of the window. `full_screen_rect`
+        // itself is left untouched (still spanning clip space -1..1) - restricting gl::Viewport
+        // to the sub-rectangle is enough to confine the blit to it, no separate UV/scale
+        // adjustment on the quad is needed
+        let (vx, vy, vw, vh) = viewport.rect;
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Viewport((vx * WINDOW_WIDTH as f32) as i32, (vy * WINDOW_HEIGHT as f32) as i32,
+                (vw * WINDOW_WIDTH as f32) as i32, (vh * WINDOW_HEIGHT as f32) as i32);
+        }
+        render_params.framebuffer.unbind();
+        render_params.framebuffer.bind_color_tex(bindings::TextureSlot::BaseColor);
+        render_params.blit_shader_program.use_program();
+        render_params.full_screen_rect.draw_indexed(gl::TRIANGLES, 6);
     }
-    render_params.framebuffer.unbind();
-    render_params.framebuffer.bind_color_tex(bindings::TextureSlot::BaseColor);
-    render_params.blit_shader_program.use_program();
-    render_params.full_screen_rect.draw_indexed(gl::TRIANGLES, 6);
 }
 
 /// Main
@@ -244,10 +557,11 @@ fn main() {
         .id();
 
     // Spawn world entity
+    let world_model_name = "resources/models/demo_scene.glb".to_string();
     world.spawn()
         .insert(Position { x: 0.0, y: 0.0, z: 0.0 })
         .insert(Velocity { x: 0.0, y: 0.0, z: 0.0 })
-        .insert(Model { name: "resources/models/demo_scene.glb".to_string() });
+        .insert(Model { name: world_model_name.clone() });
 
     // Spawn ball entities
     world.spawn()
@@ -272,9 +586,29 @@ fn main() {
 
     // Add resources
     world.insert_resource(Time::default());
+    world.insert_resource(Input::default());
     world.insert_resource(RenderParams::new());
     world.insert_resource(ModelManager::new());
 
+    // Spawn a camera entity for every camera authored in the world model, so they can be
+    // previewed with the `C` key. `ModelManager::cameras` returns the glTF node's
+    // camera-to-world transform, so invert it to get the view matrix the renderer expects
+    let gltf_cams: Vec<Entity> = world.resource_mut::<ModelManager>()
+        .cameras(&world_model_name)
+        .into_iter()
+        .map(|(transform, params)| {
+            let view = transform.invert().expect("Non-invertible glTF camera transform");
+            world.spawn().insert(GltfCameraComponent { view, params }).id()
+        })
+        .collect();
+
+    world.insert_resource(ActiveCamera::new(camera_id, gltf_cams));
+
+    // A single viewport filling the window, following whichever camera is active
+    world.insert_resource(Viewports {
+        viewports: vec![Viewport { rect: (0.0, 0.0, 1.0, 1.0), camera: camera_id }]
+    });
+
     // Create schedule
     let mut update_schedule = Schedule::default();
     update_schedule.add_stage("update", SystemStage::parallel()
@@ -295,9 +629,6 @@ fn main() {
     // Current mouse pos
     let (mut mouse_x, mut mouse_y) = window.window.get_cursor_pos();
 
-    // Camera input
-    let mut forward_held: bool = false;
-
     // TODO: might be worth doing this on click, and adding a release button
     window.set_mouse_captured(true);
 
@@ -309,36 +640,40 @@ fn main() {
                 glfw::WindowEvent::Key(Key::Escape, _, Action::Press, _) => {
                     window.window.set_should_close(true)
                 },
-                glfw::WindowEvent::Key(Key::W, _, Action::Press, _) => {
-                    forward_held = true;
+                glfw::WindowEvent::Key(Key::C, _, Action::Press, _) => {
+                    let active_entity = {
+                        let mut active_camera = world.resource_mut::<ActiveCamera>();
+                        active_camera.cycle();
+                        active_camera.active_entity()
+                    };
+
+                    world.resource_mut::<Viewports>().viewports[0].camera = active_entity;
+                },
+                glfw::WindowEvent::Key(Key::F, _, Action::Press, _) => {
+                    world.resource_mut::<RenderParams>().toggle_wireframe();
                 },
-                glfw::WindowEvent::Key(Key::W, _, Action::Release, _) => {
-                    forward_held = false;
+                glfw::WindowEvent::Key(key, _, Action::Press, _) if is_tracked_key(key) => {
+                    world.resource_mut::<Input>().keys_held.insert(key);
+                },
+                glfw::WindowEvent::Key(key, _, Action::Release, _) if is_tracked_key(key) => {
+                    world.resource_mut::<Input>().keys_held.remove(&key);
+                },
+                glfw::WindowEvent::Scroll(_, scroll_dy) => {
+                    world.resource_mut::<Input>().scroll_dy += scroll_dy;
                 },
                 _ => {}
             }
         }
 
-        // Update camera
-        // TODO: I doubt this is the best way to get input into the system tbh
+        // Apply mouse look directly to PitchYaw every real frame, rather than routing it through
+        // the fixed 30Hz update schedule - see the note on `Input` for why
         let (old_mouse_x, old_mouse_y) = (mouse_x, mouse_y);
         (mouse_x, mouse_y) = window.window.get_cursor_pos();
         let (mouse_dx, mouse_dy) = (mouse_x - old_mouse_x, mouse_y - old_mouse_y);
 
-        let mut camera_mut = world.entity_mut(camera_id);
-        let mut camera_pitch_yaw = camera_mut.get_mut::<PitchYaw>().unwrap();
-
-        camera_pitch_yaw.pitch -= mouse_dy as f32 * CAM_LOOK_SPEED;
-        camera_pitch_yaw.yaw -= mouse_dx as f32 * CAM_LOOK_SPEED;
-
-        if forward_held {
-            let cam_forward = *camera_mut.get::<CameraComponent>().unwrap().camera.forward();
-            let mut camera_pos = camera_mut.get_mut::<Position>().unwrap();
-            let new_pos = vec3(camera_pos.x, camera_pos.y, camera_pos.z) + cam_forward * CAM_FLY_SPEED;
-            camera_pos.x = new_pos.x;
-            camera_pos.y = new_pos.y;
-            camera_pos.z = new_pos.z;
-        }
+        let mut pitch_yaw = world.get_mut::<PitchYaw>(camera_id).expect("Camera entity missing PitchYaw");
+        pitch_yaw.pitch -= mouse_dy as f32 * CAM_LOOK_SPEED;
+        pitch_yaw.yaw -= mouse_dx as f32 * CAM_LOOK_SPEED;
 
         // Fixed timestep
         let new_time = window.glfw.get_time();